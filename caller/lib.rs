@@ -5,7 +5,7 @@
 #[ink::contract]
 mod call_solidity {
     use ink::env::{
-        call::{build_call, Call, ExecutionInput, Selector},
+        call::{build_call, Call, CallFlags, DelegateCall, ExecutionInput, Selector},
         debug_println, DefaultEnvironment,
     };
 
@@ -14,56 +14,204 @@ mod call_solidity {
     pub struct Caller {
         // State variable indicating the current state of the Caller contract
         state: bool,
+        /// The implementation contract that the fallback message delegates
+        /// unrecognised calls to. Upgrading the implementation is just a
+        /// matter of repointing this at a new contract's account.
+        forward_to: AccountId,
+        /// The only account allowed to change `forward_to`.
+        admin: AccountId,
     }
 
     // Implement the ink! contract for calling other contracts at runtime
     impl Caller {
         // Constructor function for initializing the Caller contract
         #[ink(constructor)]
-        pub fn new() -> Self {
+        pub fn new(forward_to: AccountId) -> Self {
             Self {
                 state: true,
+                forward_to,
+                admin: Self::env().caller(),
             }
         }
 
+        /// Repoints the proxy at a new implementation contract.
+        ///
+        /// Only the `admin` set at construction time may call this.
+        #[ink(message)]
+        pub fn change_forward_address(&mut self, forward_to: AccountId) {
+            assert_eq!(
+                self.env().caller(),
+                self.admin,
+                "caller is not the proxy admin"
+            );
+            self.forward_to = forward_to;
+        }
+
+        /// Returns the implementation contract the fallback currently forwards to.
+        #[ink(message)]
+        pub fn get_forward_address(&self) -> AccountId {
+            self.forward_to
+        }
+
+        /// Returns the account allowed to call `change_forward_address`.
+        #[ink(message)]
+        pub fn get_admin(&self) -> AccountId {
+            self.admin
+        }
+
         // Message function for delegate call to the flip contract
+        //
+        // Uses the v2 call builder so the caller can bound the callee's
+        // weight and storage deposit, and `try_invoke` so a reverting or
+        // out-of-gas callee comes back as an `Err` instead of trapping this
+        // contract.
         #[ink(message)]
         pub fn delegate_call(
             &mut self,
             callee: AccountId,
-        ) {
+            ref_time_limit: u64,
+            proof_size_limit: u64,
+            storage_deposit_limit: Option<Balance>,
+        ) -> Result<bool, ink::env::Error> {
             // Build a delegate call to the specified contract using ink! call APIs
             let my_return_value = build_call::<DefaultEnvironment>()
                 .call_type(Call::new(callee))
+                .ref_time_limit(ref_time_limit)
+                .proof_size_limit(proof_size_limit)
+                .storage_deposit_limit(storage_deposit_limit.unwrap_or(Balance::MAX))
                 .exec_input(
                     // Specify the function selector for the "flip" function in the flip contract
                     ExecutionInput::new(Selector::new(ink::selector_bytes!("flip")))
                 )
                 .returns::<bool>()
-                .invoke();
+                .try_invoke()?
+                .unwrap_or_else(|err| panic!("callee returned a `LangError`: {:?}", err));
+
+            Ok(my_return_value)
         }
 
         // Message function for static call to the flip contract and debug printing the result
+        //
+        // Same v2 call builder and `try_invoke` treatment as `delegate_call`.
         #[ink(message)]
         pub fn static_call(
             &mut self,
             callee: AccountId,
-        ) -> bool {
+            ref_time_limit: u64,
+            proof_size_limit: u64,
+            storage_deposit_limit: Option<Balance>,
+        ) -> Result<bool, ink::env::Error> {
             // Build a static call to the specified contract using ink! call APIs
             let my_return_value = build_call::<DefaultEnvironment>()
                 .call_type(Call::new(callee))
+                .ref_time_limit(ref_time_limit)
+                .proof_size_limit(proof_size_limit)
+                .storage_deposit_limit(storage_deposit_limit.unwrap_or(Balance::MAX))
                 .exec_input(
                     // Specify the function selector for the "get" function in the flip contract
                     ExecutionInput::new(Selector::new(ink::selector_bytes!("get")))
                 )
                 .returns::<bool>()
-                .invoke();
+                .try_invoke()?
+                .unwrap_or_else(|err| panic!("callee returned a `LangError`: {:?}", err));
 
             // Debug print the returned value from the static call
             debug_println!("{:?}", my_return_value);
 
             // Return the result of the static call
-            my_return_value
+            Ok(my_return_value)
+        }
+
+        /// Reports `self.env().gas_left()` immediately before and after
+        /// forwarding a `delegate_call`, so integrators can measure what a
+        /// forwarded call actually costs.
+        #[ink(message)]
+        pub fn delegate_call_with_gas_report(
+            &mut self,
+            callee: AccountId,
+            ref_time_limit: u64,
+            proof_size_limit: u64,
+            storage_deposit_limit: Option<Balance>,
+        ) -> (u64, u64, Result<bool, ink::env::Error>) {
+            let gas_before = self.env().gas_left();
+            let result =
+                self.delegate_call(callee, ref_time_limit, proof_size_limit, storage_deposit_limit);
+            let gas_after = self.env().gas_left();
+
+            (gas_before, gas_after, result)
         }
+
+        /// Fallback: forwards any call whose selector doesn't match one of this
+        /// contract's own messages to `forward_to`.
+        ///
+        /// This is issued as a delegate call against the implementation's code
+        /// hash, so the forwarded call executes against the proxy's own
+        /// storage rather than the implementation's. `forward_input` passes
+        /// the original call's selector and arguments through byte-for-byte,
+        /// and `tail_call` hands the callee's raw output straight back to
+        /// whoever called the proxy without this contract decoding or
+        /// re-encoding it. Because it's a delegate call the transferred value
+        /// never leaves this contract's balance, so no explicit value
+        /// forwarding is needed.
+        #[ink(message, payable, selector = _)]
+        pub fn forward(&mut self) {
+            let code_hash = self
+                .env()
+                .code_hash(&self.forward_to)
+                .unwrap_or_else(|err| panic!("forward_to has no code: {:?}", err));
+
+            build_call::<DefaultEnvironment>()
+                .call_type(DelegateCall::new(code_hash))
+                .call_flags(
+                    CallFlags::default()
+                        .set_forward_input(true)
+                        .set_tail_call(true),
+                )
+                .returns::<()>()
+                .invoke();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ink::env::test;
+    use ink::env::DefaultEnvironment;
+    use crate::call_solidity::Caller;
+
+    /// The deployer becomes `admin`, and `forward_to` starts out at whatever
+    /// was passed to the constructor.
+    #[ink::test]
+    fn test_constructor_sets_admin_and_forward_to() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        test::set_caller::<DefaultEnvironment>(accounts.alice);
+        let contract = Caller::new(accounts.django);
+
+        assert_eq!(contract.get_admin(), accounts.alice);
+        assert_eq!(contract.get_forward_address(), accounts.django);
+    }
+
+    /// The admin can repoint the proxy at a new implementation contract.
+    #[ink::test]
+    fn test_admin_can_change_forward_address() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        test::set_caller::<DefaultEnvironment>(accounts.alice);
+        let mut contract = Caller::new(accounts.django);
+
+        contract.change_forward_address(accounts.eve);
+
+        assert_eq!(contract.get_forward_address(), accounts.eve);
+    }
+
+    /// A non-admin caller must not be able to repoint the proxy.
+    #[ink::test]
+    #[should_panic(expected = "caller is not the proxy admin")]
+    fn test_non_admin_cannot_change_forward_address() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        test::set_caller::<DefaultEnvironment>(accounts.alice);
+        let mut contract = Caller::new(accounts.django);
+
+        test::set_caller::<DefaultEnvironment>(accounts.bob);
+        contract.change_forward_address(accounts.eve);
     }
 }
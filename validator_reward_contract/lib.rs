@@ -2,118 +2,401 @@
 #![allow(unexpected_cfgs)]
 
 
-/// This ink! smart contract implements a simple validator reward mechanism,
+/// This ink! smart contract implements a validator reward mechanism,
 /// designed to integrate with the PoCS (Proof of Contract Stake) system. In PoCS,
 /// smart contracts contribute to network security by participating in staking,
 /// and this contract provides a framework for claiming rewards.
 ///
-/// The contract stores the last claim block for each account, and only allows a claim
-/// if a sufficient number of blocks have passed since the previous claim (a cooldown mechanism).
-/// Additional PoCS-related logic, such as incorporating stake scores, reputation,
-/// and further validator interactions, can be extended into this contract.
+/// Rewards accrue with a standard reward-per-token accumulator: the owner
+/// funds a reward period with `notify_reward_amount`, and every
+/// state-changing call first brings the global accumulator and the caller's
+/// pending rewards up to date before doing anything else. This makes the
+/// payout proportional to how much an account has staked and for how long,
+/// rather than a flat amount per claim.
+///
+/// Staking and reward withdrawal are kept strictly separate: `stake` and
+/// `unstake` only ever move an account's bonded principal; they credit the
+/// reward accrued so far to `pending_rewards` but never pay it out or reduce
+/// it. Accrued rewards sit in `pending_rewards` until an account calls
+/// `withdraw_rewards`, so bonding/unbonding never implicitly triggers or
+/// blocks a payout.
+///
+/// Additional PoCS-related logic, such as further validator interactions,
+/// can be extended into this contract.
 
 #[ink::contract]
 mod rewardclaimer {
 
+    /// Scales `reward_per_token_stored` so that integer division in
+    /// `reward_per_token` doesn't truncate away small per-block rates.
+    const SCALING_FACTOR: Balance = 1_000_000_000_000;
+
+    /// Emitted once a caller's delegation to the contract owner is recognised
+    /// by `claim`, before any reward is computed.
+    #[ink(event)]
+    pub struct Delegated {
+        #[ink(topic)]
+        pub validator: AccountId,
+        #[ink(topic)]
+        pub account: AccountId,
+    }
+
+    /// Emitted when `withdraw_rewards` actually pays an account its pending reward.
+    #[ink(event)]
+    pub struct RewardClaimed {
+        #[ink(topic)]
+        pub account: AccountId,
+        pub amount: Balance,
+        pub block: BlockNumber,
+    }
+
     /// The RewardClaimContract structure holds the state of the contract.
-    /// 
+    ///
     /// - `owner`: The account that deployed the contract and is considered the primary administrator.
-    /// - `last_claimed_block`: A mapping that records the last block number when an account made a claim.
-    /// - `claim_amount`: The fixed amount of tokens (in standard units) awarded per valid claim.
-    /// 
+    /// - `reward_rate`: Reward tokens emitted per block while a reward period is active
+    ///   (total reward budget / reward duration).
+    /// - `reward_per_token_stored`: The accumulated reward per staked unit, scaled by
+    ///   `SCALING_FACTOR`, as of `last_update_block`.
+    /// - `last_update_block`: The block at which `reward_per_token_stored` was last brought
+    ///   up to date.
+    /// - `period_finish`: The block at which the current reward period runs out.
+    /// - `total_staked`: The sum of every account's recognised stake.
+    /// - `staked`: Each account's recognised stake.
+    /// - `user_reward_per_token_paid`: The value of `reward_per_token_stored` already
+    ///   accounted for against each account.
+    /// - `pending_rewards`: Reward owed to each account but not yet withdrawn.
+    /// - `reward_pool_per_block`: The PoCS reward budget available per block, shared across
+    ///   all contracts in proportion to `staking_score` / `total_staking_score`.
+    /// - `total_staking_score`: The sum of every contract's `staking_score`, as tracked by
+    ///   the PoCS consensus layer.
+    /// - `reputation_floor`: Added to a caller's reputation before scaling, so a
+    ///   reputation of `0` still earns a non-zero share of its staking-weighted base reward.
+    /// - `reputation_scale`: Divides the reputation-adjusted numerator back down to a
+    ///   multiplier around `1x`.
+    /// - `max_reputation_multiplier`: Caps how many times the staking-weighted base reward
+    ///   a high reputation can multiply it by.
+    /// - `cooldown_blocks`: The minimum number of blocks that must pass between two
+    ///   claims from the same account.
+    /// - `last_claimed_block`: The last block number at which each account claimed.
+    ///
     #[ink(storage)]
     pub struct RewardClaimContract {
         owner: AccountId,
-        last_claimed_block: ink::storage::Mapping<AccountId, u32>,
-        claim_amount: Balance,
+        reward_rate: Balance,
+        reward_per_token_stored: Balance,
+        last_update_block: BlockNumber,
+        period_finish: BlockNumber,
+        total_staked: Balance,
+        staked: ink::storage::Mapping<AccountId, Balance>,
+        user_reward_per_token_paid: ink::storage::Mapping<AccountId, Balance>,
+        pending_rewards: ink::storage::Mapping<AccountId, Balance>,
+        reward_pool_per_block: Balance,
+        total_staking_score: u128,
+        reputation_floor: u64,
+        reputation_scale: u64,
+        max_reputation_multiplier: u64,
+        cooldown_blocks: BlockNumber,
+        last_claimed_block: ink::storage::Mapping<AccountId, BlockNumber>,
     }
 
     impl RewardClaimContract {
 
         /// The constructor initializes the contract.
-        /// It sets the contract owner, initializes the last claim mapping, and sets a default claim amount.
-        /// 
+        /// It sets the contract owner; every accumulator field starts at zero
+        /// until the owner funds a reward period with `notify_reward_amount`.
+        /// The reputation multiplier defaults to `(1 + reputation)x`, so a
+        /// reputation of `0` earns a neutral `1x`, bounded to at most `3x`,
+        /// and the claim cooldown defaults to 10 blocks.
+        ///
         /// # Parameters
         /// - `owner`: The account ID that will own the contract.
-        /// 
+        ///
         #[ink(constructor)]
         pub fn new(owner: AccountId) -> Self {
             Self {
                 owner,
+                reward_rate: 0,
+                reward_per_token_stored: 0,
+                last_update_block: 0,
+                period_finish: 0,
+                total_staked: 0,
+                staked: ink::storage::Mapping::new(),
+                user_reward_per_token_paid: ink::storage::Mapping::new(),
+                pending_rewards: ink::storage::Mapping::new(),
+                reward_pool_per_block: 0,
+                total_staking_score: 0,
+                reputation_floor: 1,
+                reputation_scale: 1,
+                max_reputation_multiplier: 3,
+                cooldown_blocks: 10,
                 last_claimed_block: ink::storage::Mapping::new(),
-                claim_amount: 10, // This value can be modified or made dynamic for more advanced use cases.
             }
         }
 
-        /// The `claim` function is responsible for processing reward claims.
+        /// Sets the per-block PoCS reward budget shared across every
+        /// staking contract. Owner-only.
+        #[ink(message)]
+        pub fn set_reward_pool_per_block(&mut self, reward_pool_per_block: Balance) {
+            assert_eq!(self.env().caller(), self.owner, "caller is not the owner");
+            self.reward_pool_per_block = reward_pool_per_block;
+        }
+
+        /// Sets the PoCS-wide total staking score that per-contract staking
+        /// scores are weighed against. Owner-only.
+        #[ink(message)]
+        pub fn set_total_staking_score(&mut self, total_staking_score: u128) {
+            assert_eq!(self.env().caller(), self.owner, "caller is not the owner");
+            self.total_staking_score = total_staking_score;
+        }
+
+        /// Configures the reputation multiplier bounds used to scale the
+        /// staking-weighted base reward. Owner-only.
+        ///
+        /// # Parameters
+        /// - `reputation_floor`: Added to a caller's reputation before scaling.
+        /// - `reputation_scale`: Divides the result back down to a multiplier around `1x`.
+        /// - `max_reputation_multiplier`: Caps the multiplier at this many times `1x`.
+        #[ink(message)]
+        pub fn set_reputation_bounds(
+            &mut self,
+            reputation_floor: u64,
+            reputation_scale: u64,
+            max_reputation_multiplier: u64,
+        ) {
+            assert_eq!(self.env().caller(), self.owner, "caller is not the owner");
+            assert!(reputation_scale > 0, "reputation_scale must be greater than zero");
+
+            self.reputation_floor = reputation_floor;
+            self.reputation_scale = reputation_scale;
+            self.max_reputation_multiplier = max_reputation_multiplier;
+        }
+
+        /// Sets the minimum number of blocks that must pass between two
+        /// claims from the same account. Owner-only.
+        #[ink(message)]
+        pub fn set_cooldown_blocks(&mut self, cooldown_blocks: BlockNumber) {
+            assert_eq!(self.env().caller(), self.owner, "caller is not the owner");
+            self.cooldown_blocks = cooldown_blocks;
+        }
+
+        /// Funds a new reward period, recomputing `reward_rate` over `amount`
+        /// and `duration` blocks.
+        ///
+        /// If a previous period is still in progress, its undistributed
+        /// remainder is rolled into the new rate rather than discarded.
+        ///
+        /// # Parameters
+        /// - `amount`: The total reward budget for the new period.
+        /// - `duration`: How many blocks the new period should run for.
+        #[ink(message)]
+        pub fn notify_reward_amount(&mut self, amount: Balance, duration: BlockNumber) {
+            assert_eq!(self.env().caller(), self.owner, "caller is not the owner");
+            assert!(duration > 0, "duration must be greater than zero");
+
+            self.update_reward_accumulator();
+
+            let current_block = self.env().block_number();
+            if current_block >= self.period_finish {
+                self.reward_rate = amount / duration as Balance;
+            } else {
+                let remaining_blocks = (self.period_finish - current_block) as Balance;
+                let leftover = remaining_blocks
+                    .checked_mul(self.reward_rate)
+                    .expect("remaining_blocks * reward_rate overflow");
+                self.reward_rate = amount
+                    .checked_add(leftover)
+                    .expect("amount + leftover overflow")
+                    / duration as Balance;
+            }
+
+            self.last_update_block = current_block;
+            self.period_finish = current_block + duration;
+        }
+
+        /// Bonds `amount` of the caller's transferred value as recognised
+        /// stake. Only ever moves the caller's principal; it credits the
+        /// reward accrued so far to `pending_rewards` but never pays it out.
+        ///
+        /// # Parameters
+        /// - `amount`: How much of the transferred value to bond. Must equal
+        ///   the value actually transferred with the call.
+        #[ink(message, payable)]
+        pub fn stake(&mut self, amount: Balance) {
+            assert_eq!(
+                self.env().transferred_value(),
+                amount,
+                "transferred value does not match the amount to stake"
+            );
+
+            let caller = self.env().caller();
+            self.update_reward(caller);
+
+            let previous = self.staked.get(caller).unwrap_or(0);
+            self.staked.insert(caller, &(previous + amount));
+            self.total_staked += amount;
+        }
+
+        /// Unbonds `amount` of the caller's recognised stake and returns it
+        /// to the caller. Only ever moves the caller's principal; it credits
+        /// the reward accrued so far to `pending_rewards` but never pays it out.
+        ///
+        /// # Parameters
+        /// - `amount`: How much recognised stake to unbond.
+        #[ink(message)]
+        pub fn unstake(&mut self, amount: Balance) {
+            let caller = self.env().caller();
+            self.update_reward(caller);
+
+            let previous = self.staked.get(caller).unwrap_or(0);
+            assert!(previous >= amount, "not enough staked to unstake that much");
+
+            self.staked.insert(caller, &(previous - amount));
+            self.total_staked -= amount;
+
+            self.env()
+                .transfer(caller, amount)
+                .expect("Transfer failed");
+        }
+
+        /// Pays out the caller's entire `pending_rewards` balance, zeroes it,
+        /// and emits a `RewardClaimed` event recording what was paid.
+        #[ink(message)]
+        pub fn withdraw_rewards(&mut self) {
+            let caller = self.env().caller();
+            self.update_reward(caller);
+
+            let amount = self.pending_rewards.get(caller).unwrap_or(0);
+            if amount > 0 {
+                self.pending_rewards.insert(caller, &0);
+                self.env()
+                    .transfer(caller, amount)
+                    .expect("Transfer failed");
+
+                self.env().emit_event(RewardClaimed {
+                    account: caller,
+                    amount,
+                    block: self.env().block_number(),
+                });
+            }
+        }
+
+        /// The `claim` function gates reward accrual on the caller's PoCS delegation.
         ///
         /// This function checks if the `delegated_validator` account matches the contract owner,
-        /// verifies that enough blocks have passed since the caller's last claim,
-        /// and if so, transfers the reward to the provided `owner` account.
+        /// and if so, brings the caller's staking-accumulator `pending_rewards` up to date and,
+        /// subject to `cooldown_blocks`, credits a weighted PoCS reward on top of it based on
+        /// `staking_score` and `reputation`. It never moves tokens itself; call
+        /// `withdraw_rewards` separately to pay the accrued amount out.
+        ///
+        /// The weighted reward is `reward_pool_per_block * blocks_elapsed * staking_score /
+        /// total_staking_score`, then scaled by a reputation multiplier of
+        /// `(reputation_floor + reputation) / reputation_scale`, clamped to
+        /// `max_reputation_multiplier`. This way a low-reputation contract earns a smaller
+        /// share of the per-block pool and a high-reputation one earns more, without letting
+        /// reputation inflate rewards without bound.
         ///
         /// # Parameters
-        /// - `owner`: The account that receives the reward.
+        /// - `owner`: A payout destination; ignored, since `withdraw_rewards` always pays the caller (see remarks).
         /// - `delegated_validator`: The account that the caller is delegating to. Must match the contract owner.
         /// - `delegation_block`: The block number when delegation occurred (currently unused).
-        /// - `reputation`: The reputation value of the caller's contract (currently unused but can be integrated into reward logic).
+        /// - `reputation`: The reputation value of the caller's contract, used to scale the reward.
         /// - `current_block_height`: Recent block number for additional logic (currently unused).
-        /// - `staking_score`: The staking score of the caller (currently unused but can be used for weighted rewards).
+        /// - `staking_score`: The staking score of the caller, used to weight its share of the reward pool.
         ///
         /// # Remarks
-        /// This basic implementation demonstrates a reward mechanism that could be expanded to include
-        /// more detailed checks using PoCS parameters (e.g., reputation, staking_score) in the future.
-        /// 
+        /// `owner` is kept for interface compatibility with callers that pass a payout
+        /// destination; reward transfers now happen exclusively through `withdraw_rewards`.
         #[ink(message)]
         pub fn claim(
             &mut self,
-            owner: AccountId,
+            _owner: AccountId,
             delegated_validator: AccountId,
             _delegation_block: BlockNumber,
-            _reputation: u64,
+            reputation: u64,
             _current_block_height: BlockNumber,
-            _staking_score: u128,
+            staking_score: u128,
         ) {
             // Retrieve the account making the claim.
             let caller = self.env().caller();
-            
+
             // Check if the delegation is correctly set to the contract owner.
             if delegated_validator == self.owner {
+                self.env().emit_event(Delegated {
+                    validator: delegated_validator,
+                    account: caller,
+                });
+
+                self.update_reward(caller);
+
                 let current_block = self.env().block_number();
-                // Retrieve the last claim block for the caller, defaulting to 0 if none exists.
-                let last_claim = self.last_claimed_block.get(caller).unwrap_or(0);
-                // Calculate the number of blocks that have passed since the last claim.
-                let range = current_block.saturating_sub(last_claim);
-                // Allow the claim if the caller has never claimed before or if more than 10 blocks have passed.
-                if last_claim == 0 || range > 10 {
-                    // Update the mapping with the current block number as the last claim.
+                let never_claimed = self.last_claimed_block.get(caller).is_none();
+                // An account that has never claimed before has, by construction, never
+                // participated for longer than one cooldown window: anchoring it to
+                // genesis instead would let a claim delayed until a high block number
+                // collect `blocks_elapsed` worth of reward it never actually earned.
+                let last_claim = self
+                    .last_claimed_block
+                    .get(caller)
+                    .unwrap_or_else(|| current_block.saturating_sub(self.cooldown_blocks));
+                let blocks_elapsed = current_block.saturating_sub(last_claim);
+
+                if never_claimed || blocks_elapsed >= self.cooldown_blocks {
                     self.last_claimed_block.insert(caller, &current_block);
-                    // Convert the claim amount into the smallest unit. This is essential for precise token transfers.
-                    let claim_amount_in_smallest_units = self
-                        .claim_amount
-                        .checked_mul(1_000_000_000_000)
-                        .expect("Overflow during multiplication");
-                    // Transfer the reward to the specified owner account.
-                    self.env()
-                        .transfer(owner, claim_amount_in_smallest_units)
-                        .expect("Transfer failed");
+
+                    let weighted = self.weighted_reward(staking_score, reputation, blocks_elapsed.max(1));
+                    if weighted > 0 {
+                        let pending = self.pending_rewards.get(caller).unwrap_or(0);
+                        self.pending_rewards.insert(
+                            caller,
+                            &pending.checked_add(weighted).expect("pending reward overflow"),
+                        );
+                    }
                 }
             }
-            // Future enhancements can integrate parameters such as `delegation_block`, `reputation`,
-            // `current_block_height`, and `staking_score` to provide more nuanced reward calculations
-            // in line with PoCS consensus mechanisms.
         }
 
-        /// Returns the last block number when the specified account made a claim.
-        ///
-        /// # Parameters
-        /// - `account`: The account ID for which to retrieve the last claim block.
-        ///
-        /// # Returns
-        /// The block number of the last claim, or 0 if no claim has been made.
-        /// 
+        /// Computes the weighted PoCS reward for a single claim: a staking-score-proportional
+        /// share of the per-block pool over `blocks_elapsed`, scaled by a bounded reputation
+        /// multiplier. Returns `0` if no staking score has been registered PoCS-wide yet.
+        fn weighted_reward(
+            &self,
+            staking_score: u128,
+            reputation: u64,
+            blocks_elapsed: BlockNumber,
+        ) -> Balance {
+            if self.total_staking_score == 0 {
+                return 0;
+            }
+
+            let base = self
+                .reward_pool_per_block
+                .checked_mul(blocks_elapsed as Balance)
+                .expect("reward_pool_per_block * blocks_elapsed overflow")
+                .checked_mul(staking_score)
+                .expect("base * staking_score overflow")
+                / self.total_staking_score;
+
+            let uncapped_numerator = (self.reputation_floor as u128)
+                .checked_add(reputation as u128)
+                .expect("reputation_floor + reputation overflow");
+            let capped_numerator = uncapped_numerator
+                .min((self.max_reputation_multiplier as u128) * (self.reputation_scale as u128));
+
+            base.checked_mul(capped_numerator)
+                .expect("base * reputation multiplier overflow")
+                / self.reputation_scale as Balance
+        }
+
+        /// Returns `account`'s recognised stake.
+        #[ink(message)]
+        pub fn get_staked(&self, account: AccountId) -> Balance {
+            self.staked.get(account).unwrap_or(0)
+        }
+
+        /// Returns the reward `account` has accrued and not yet withdrawn, as of the current block.
         #[ink(message)]
-        pub fn get_last_claimed_block(&self, account: AccountId) -> u32 {
-            self.last_claimed_block.get(account).unwrap_or(0)
+        pub fn get_earned(&self, account: AccountId) -> Balance {
+            self.earned(account)
         }
 
         /// Returns the current block number from the blockchain environment.
@@ -131,6 +414,67 @@ mod rewardclaimer {
         pub fn get_contract_account(&self) -> AccountId {
             self.env().account_id()
         }
+
+        /// Brings the global reward-per-token accumulator up to date with the
+        /// current block, without touching any account's pending reward.
+        fn update_reward_accumulator(&mut self) {
+            self.reward_per_token_stored = self.reward_per_token();
+            self.last_update_block = self.env().block_number().min(self.period_finish);
+        }
+
+        /// Brings the global accumulator up to date, then credits `account`
+        /// with everything it has earned since its last update.
+        fn update_reward(&mut self, account: AccountId) {
+            self.update_reward_accumulator();
+
+            let earned = self.earned(account);
+            self.pending_rewards.insert(account, &earned);
+            self.user_reward_per_token_paid
+                .insert(account, &self.reward_per_token_stored);
+        }
+
+        /// The reward-per-token value as of the current block, without
+        /// mutating storage.
+        fn reward_per_token(&self) -> Balance {
+            if self.total_staked == 0 {
+                return self.reward_per_token_stored;
+            }
+
+            let now = self.env().block_number().min(self.period_finish);
+            let elapsed = now.saturating_sub(self.last_update_block) as Balance;
+            let accrued = self
+                .reward_rate
+                .checked_mul(elapsed)
+                .expect("reward_rate * elapsed overflow")
+                .checked_mul(SCALING_FACTOR)
+                .expect("accrued * SCALING_FACTOR overflow")
+                / self.total_staked;
+
+            self.reward_per_token_stored
+                .checked_add(accrued)
+                .expect("reward_per_token_stored + accrued overflow")
+        }
+
+        /// The reward `account` has earned up to the current block, without
+        /// mutating storage.
+        fn earned(&self, account: AccountId) -> Balance {
+            let staked = self.staked.get(account).unwrap_or(0);
+            let paid = self.user_reward_per_token_paid.get(account).unwrap_or(0);
+            let pending = self.pending_rewards.get(account).unwrap_or(0);
+
+            let delta = self
+                .reward_per_token()
+                .checked_sub(paid)
+                .expect("reward_per_token went backwards relative to user_reward_per_token_paid");
+            let accrued = staked
+                .checked_mul(delta)
+                .expect("staked * reward_per_token delta overflow")
+                / SCALING_FACTOR;
+
+            accrued
+                .checked_add(pending)
+                .expect("accrued + pending overflow")
+        }
     }
 }
 
@@ -138,7 +482,8 @@ mod rewardclaimer {
 mod tests {
     use ink::env::test;
     use ink::env::DefaultEnvironment;
-    use crate::rewardclaimer::RewardClaimContract;
+    use ink::scale;
+    use crate::rewardclaimer::{Delegated, RewardClaimContract, RewardClaimed};
 
     /// Helper function to simulate advancing the blockchain by `n` blocks.
     fn advance_blocks(n: u32) {
@@ -147,72 +492,252 @@ mod tests {
         }
     }
 
-    /// Test case to verify that an account can successfully make a claim
-    /// if it has not claimed before.
+    /// An account with no recognised stake never earns anything, even once a
+    /// reward period is funded.
     #[ink::test]
-    fn test_first_claim() {
+    fn test_no_stake_no_reward() {
         let accounts = test::default_accounts::<DefaultEnvironment>();
         let contract_owner = accounts.alice;
         let caller = accounts.bob;
-        test::set_caller::<DefaultEnvironment>(caller);
+        test::set_caller::<DefaultEnvironment>(contract_owner);
+        let mut contract = RewardClaimContract::new(contract_owner);
+
+        contract.notify_reward_amount(1_000, 10);
+        advance_blocks(5);
+
+        assert_eq!(contract.get_earned(caller), 0);
+    }
+
+    /// Reward accrues proportionally to stake and elapsed blocks, and
+    /// `withdraw_rewards` pays out exactly the accrued amount before zeroing it.
+    #[ink::test]
+    fn test_withdraw_rewards_pays_accrued_reward() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let contract_owner = accounts.alice;
+        let caller = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
         let mut contract = RewardClaimContract::new(contract_owner);
 
         let contract_account = contract.get_contract_account();
-        test::set_account_balance::<DefaultEnvironment>(
-            contract_account,
-            1_000_000_000_000_000,
-        );
-
-        let current_block = contract.get_current_block();
-        contract.claim(contract_owner, contract_owner, 0, 0, 0, 0);
-        let last_claim = contract.get_last_claimed_block(caller);
-        assert_eq!(last_claim, current_block);
+        test::set_account_balance::<DefaultEnvironment>(contract_account, 1_000_000_000_000_000);
+
+        contract.notify_reward_amount(1_000, 10);
+
+        test::set_caller::<DefaultEnvironment>(caller);
+        test::set_value_transferred::<DefaultEnvironment>(100);
+        contract.stake(100);
+
+        advance_blocks(5);
+        let expected = contract.get_earned(caller);
+        assert!(expected > 0);
+
+        let balance_before = test::get_account_balance::<DefaultEnvironment>(caller)
+            .expect("caller should have a balance");
+        contract.withdraw_rewards();
+        let balance_after = test::get_account_balance::<DefaultEnvironment>(caller)
+            .expect("caller should have a balance");
+
+        assert_eq!(balance_after - balance_before, expected);
+        assert_eq!(contract.get_earned(caller), 0);
     }
 
-    /// Test case to verify that an account cannot claim again too soon.
+    /// `claim` only brings pending rewards up to date; it never transfers.
+    /// Withdrawing is still required to actually receive the payout,
+    /// regardless of whether the delegation check passed.
     #[ink::test]
-    fn test_claim_too_soon() {
+    fn test_claim_never_transfers() {
         let accounts = test::default_accounts::<DefaultEnvironment>();
         let contract_owner = accounts.alice;
         let caller = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
+        let mut contract = RewardClaimContract::new(contract_owner);
+
+        let contract_account = contract.get_contract_account();
+        test::set_account_balance::<DefaultEnvironment>(contract_account, 1_000_000_000_000_000);
+
+        contract.notify_reward_amount(1_000, 10);
+
         test::set_caller::<DefaultEnvironment>(caller);
+        test::set_value_transferred::<DefaultEnvironment>(100);
+        contract.stake(100);
+        advance_blocks(5);
+
+        contract.claim(caller, contract_owner, 0, 0, 0, 0);
+
+        // The accrued reward is still sitting in `pending_rewards`, untouched.
+        assert!(contract.get_earned(caller) > 0);
+    }
+
+    /// Unstaking returns exactly the principal requested and leaves any
+    /// accrued, unwithdrawn reward untouched.
+    #[ink::test]
+    fn test_unstake_does_not_touch_pending_rewards() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let contract_owner = accounts.alice;
+        let caller = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
         let mut contract = RewardClaimContract::new(contract_owner);
+
         let contract_account = contract.get_contract_account();
         test::set_account_balance::<DefaultEnvironment>(contract_account, 1_000_000_000_000_000);
 
-        advance_blocks(1);
-        contract.claim(contract_owner, contract_owner, 0, 0, 0, 0);
-        let initial_claim = contract.get_last_claimed_block(caller);
+        contract.notify_reward_amount(1_000, 10);
 
+        test::set_caller::<DefaultEnvironment>(caller);
+        test::set_value_transferred::<DefaultEnvironment>(100);
+        contract.stake(100);
         advance_blocks(5);
-        contract.claim(contract_owner, contract_owner, 0, 0, 0, 0);
-        let last_claim = contract.get_last_claimed_block(caller);
-        // The claim should not be updated if not enough blocks have passed.
-        assert_eq!(last_claim, initial_claim);
+
+        contract.unstake(100);
+
+        assert_eq!(contract.get_staked(caller), 0);
+        assert!(contract.get_earned(caller) > 0);
     }
 
-    /// Test case to verify that an account can claim successfully after waiting the required blocks.
+    /// A higher `staking_score` and a higher `reputation` both increase the
+    /// weighted PoCS reward credited by `claim`.
     #[ink::test]
-    fn test_claim_after_wait() {
+    fn test_claim_weights_reward_by_score_and_reputation() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let contract_owner = accounts.alice;
+        let low = accounts.bob;
+        let high = accounts.charlie;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
+        let mut contract = RewardClaimContract::new(contract_owner);
+
+        contract.set_reward_pool_per_block(1_000);
+        contract.set_total_staking_score(100);
+        contract.set_reputation_bounds(1, 1, 3);
+
+        advance_blocks(1);
+
+        test::set_caller::<DefaultEnvironment>(low);
+        contract.claim(low, contract_owner, 0, 0, 0, 10);
+
+        test::set_caller::<DefaultEnvironment>(high);
+        contract.claim(high, contract_owner, 0, 2, 0, 50);
+
+        assert!(contract.get_earned(high) > contract.get_earned(low));
+    }
+
+    /// A second claim inside `cooldown_blocks` does not credit any further
+    /// weighted reward.
+    #[ink::test]
+    fn test_claim_weighted_reward_respects_cooldown() {
         let accounts = test::default_accounts::<DefaultEnvironment>();
         let contract_owner = accounts.alice;
         let caller = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
+        let mut contract = RewardClaimContract::new(contract_owner);
+
+        contract.set_reward_pool_per_block(1_000);
+        contract.set_total_staking_score(100);
+
+        advance_blocks(1);
         test::set_caller::<DefaultEnvironment>(caller);
+        contract.claim(caller, contract_owner, 0, 0, 0, 10);
+        let first = contract.get_earned(caller);
+        assert!(first > 0);
+
+        advance_blocks(1);
+        contract.claim(caller, contract_owner, 0, 0, 0, 10);
+
+        assert_eq!(contract.get_earned(caller), first);
+    }
+
+    /// A first-ever claim delayed until a high block number must not be
+    /// credited for every block since genesis — it should earn no more than
+    /// one `cooldown_blocks` window's worth of weighted reward, regardless of
+    /// how long the account waited before calling `claim` for the first time.
+    #[ink::test]
+    fn test_first_claim_does_not_drain_pool_by_delaying() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let contract_owner = accounts.alice;
+        let delayed_claimer = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
+        let mut contract = RewardClaimContract::new(contract_owner);
+
+        contract.set_reward_pool_per_block(1_000);
+        contract.set_total_staking_score(100);
+        contract.set_reputation_bounds(1, 1, 3);
+        contract.set_cooldown_blocks(10);
+
+        // `delayed_claimer` never claimed before, and only shows up after a
+        // long run of blocks the contract was never staked for. If
+        // `blocks_elapsed` were anchored to genesis, this would pay out
+        // `reward_pool_per_block * 1_000 * staking_score / total_staking_score`.
+        advance_blocks(1_000);
+        test::set_caller::<DefaultEnvironment>(delayed_claimer);
+        contract.claim(delayed_claimer, contract_owner, 0, 0, 0, 10);
+        let delayed_reward = contract.get_earned(delayed_claimer);
+
+        // Capped as if only one `cooldown_blocks` window had elapsed:
+        // reward_pool_per_block * cooldown_blocks * staking_score / total_staking_score.
+        let max_reward_for_one_cooldown_window = 1_000 * 10 * 10 / 100;
+        assert_eq!(delayed_reward, max_reward_for_one_cooldown_window);
+    }
+
+    /// `withdraw_rewards` emits a `RewardClaimed` event whose topic and
+    /// amount match what was actually transferred.
+    #[ink::test]
+    fn test_withdraw_rewards_emits_reward_claimed_event() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let contract_owner = accounts.alice;
+        let caller = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
         let mut contract = RewardClaimContract::new(contract_owner);
+
         let contract_account = contract.get_contract_account();
-        test::set_account_balance::<DefaultEnvironment>(
-            contract_account,
-            1_000_000_000_000_000,
-        );
-
-        contract.claim(contract_owner, contract_owner, 0, 0, 0, 0);
-        let _initial_claim = contract.get_last_claimed_block(caller);
-
-        advance_blocks(10);
-        contract.claim(contract_owner, contract_owner, 0, 0, 0, 0);
-        let current_block = contract.get_current_block();
-        let last_claim = contract.get_last_claimed_block(caller);
-        // After waiting, the claim should be updated to the current block.
-        assert_eq!(last_claim, current_block);
+        test::set_account_balance::<DefaultEnvironment>(contract_account, 1_000_000_000_000_000);
+
+        contract.notify_reward_amount(1_000, 10);
+
+        test::set_caller::<DefaultEnvironment>(caller);
+        test::set_value_transferred::<DefaultEnvironment>(100);
+        contract.stake(100);
+        advance_blocks(5);
+
+        let expected_amount = contract.get_earned(caller);
+        contract.withdraw_rewards();
+
+        let emitted_events = test::recorded_events().collect::<Vec<_>>();
+        let event = emitted_events
+            .last()
+            .expect("withdraw_rewards should have emitted a RewardClaimed event");
+
+        let decoded: RewardClaimed =
+            scale::Decode::decode(&mut &event.data[..]).expect("invalid RewardClaimed encoding");
+        assert_eq!(decoded.account, caller);
+        assert_eq!(decoded.amount, expected_amount);
+
+        // One topic for `#[ink(topic)] account`, plus the event signature topic.
+        assert_eq!(event.topics.len(), 2);
+    }
+
+    /// `claim` emits a `Delegated` event once the caller's delegation is
+    /// recognised as pointing at the contract owner.
+    #[ink::test]
+    fn test_claim_emits_delegated_event() {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let contract_owner = accounts.alice;
+        let caller = accounts.bob;
+        test::set_caller::<DefaultEnvironment>(contract_owner);
+        let mut contract = RewardClaimContract::new(contract_owner);
+
+        test::set_caller::<DefaultEnvironment>(caller);
+        contract.claim(caller, contract_owner, 0, 0, 0, 0);
+
+        let emitted_events = test::recorded_events().collect::<Vec<_>>();
+        let event = emitted_events
+            .first()
+            .expect("claim should have emitted a Delegated event");
+
+        let decoded: Delegated =
+            scale::Decode::decode(&mut &event.data[..]).expect("invalid Delegated encoding");
+        assert_eq!(decoded.validator, contract_owner);
+        assert_eq!(decoded.account, caller);
+
+        // One topic each for `validator` and `account`, plus the event signature topic.
+        assert_eq!(event.topics.len(), 3);
     }
 }